@@ -1,4 +1,8 @@
 use clap::{Parser, Subcommand};
+use clap_complete::{engine::ArgValueCompleter, Shell};
+
+use crate::config::Config;
+use crate::handler::{collect_leaf_paths, TRASH_KEY};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -8,8 +12,13 @@ use clap::{Parser, Subcommand};
 )]
 pub struct Cli {
     /// Key to yank (default action)
+    #[arg(add = ArgValueCompleter::new(complete_stored_keys))]
     pub key: Option<String>,
 
+    /// Yank into the PRIMARY selection instead of CLIPBOARD
+    #[arg(long)]
+    pub primary: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -28,8 +37,96 @@ pub enum Commands {
     Delete {
         /// The key to delete
         key: String,
+
+        /// Permanently delete instead of moving to the trash
+        #[arg(long)]
+        purge: bool,
+    },
+
+    /// Restore a trashed key
+    Restore {
+        /// The key to restore
+        key: String,
+    },
+
+    /// List trashed keys
+    Trash,
+
+    /// Store the current system clipboard contents under a key
+    Grab {
+        /// The key to store the clipboard contents under
+        key: String,
     },
 
     /// List all stored keys
-    Ls,
+    Ls {
+        /// Only list keys under this namespace (e.g. `aws.` or `aws`)
+        prefix: Option<String>,
+
+        /// Pretty-print as a nested tree instead of flat dot-paths
+        #[arg(long)]
+        tree: bool,
+    },
+
+    /// Show which clipboard provider yank will use
+    Provider,
+
+    /// Generate shell completion scripts
+    Completions {
+        /// The shell to generate completions for
+        shell: Shell,
+    },
+
+    /// Generate a man page
+    Man,
+
+    /// Open the store in $EDITOR for bulk editing
+    Edit,
+}
+
+/// Dynamic completion candidates for the `key` argument: the full dot-paths
+/// (e.g. `aws.prod.token`) of every value currently stored in `data.json`.
+/// Best-effort; any failure to read the store just yields no candidates
+/// instead of breaking completion.
+fn complete_stored_keys(current: &std::ffi::OsStr) -> Vec<clap_complete::engine::CompletionCandidate> {
+    let Some(current) = current.to_str() else {
+        return Vec::new();
+    };
+
+    let data_path = match Config::load() {
+        Ok(Config { data_path: Some(path), .. }) => path,
+        Ok(_) => {
+            let Some(home) = dirs::home_dir() else {
+                return Vec::new();
+            };
+            home.join(".yank/data.json")
+        }
+        Err(_) => return Vec::new(),
+    };
+
+    let Ok(content) = std::fs::read_to_string(data_path) else {
+        return Vec::new();
+    };
+
+    let Ok(data) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+
+    let Some(map) = data.as_object() else {
+        return Vec::new();
+    };
+
+    let mut paths = Vec::new();
+    for (key, value) in map {
+        if key == TRASH_KEY {
+            continue;
+        }
+        collect_leaf_paths(value, key.clone(), &mut paths);
+    }
+
+    paths
+        .into_iter()
+        .filter(|path| path.starts_with(current))
+        .map(clap_complete::engine::CompletionCandidate::new)
+        .collect()
 }
@@ -0,0 +1,47 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+use serde::Deserialize;
+
+use crate::handler::{Result, YankError};
+
+/// User overrides loaded from `~/.yank/config.toml`. Every field is
+/// optional; an absent or missing config file falls back to the existing
+/// hard-coded defaults.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Override the default `~/.yank/data.json` location.
+    pub data_path: Option<PathBuf>,
+
+    /// Pin a specific clipboard backend instead of auto-detecting one.
+    #[serde(default)]
+    pub provider: ProviderOverride,
+
+    /// Editor command used by `yank edit`, checked before `$VISUAL`/`$EDITOR`.
+    pub editor: Option<String>,
+}
+
+/// A forced clipboard backend, named after the binary it shells out to.
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProviderOverride {
+    #[default]
+    Auto,
+    WlCopy,
+    Xclip,
+    Xsel,
+    Pbcopy,
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        let home = dirs::home_dir().ok_or(YankError::HomeDirNotFound)?;
+        let path = home.join(".yank/config.toml");
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Ok(toml::from_str(&content)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(YankError::Io(e)),
+        }
+    }
+}
@@ -1,14 +1,24 @@
 use std::{
+    fmt,
     fs,
     io::{self, ErrorKind},
     path::PathBuf,
-    time::{Duration, Instant},
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use arboard::{Clipboard, SetExtLinux};
 use serde_json::Value;
 use thiserror::Error;
 
+use crate::config::{Config, ProviderOverride};
+
+/// Key under which soft-deleted entries are kept inside `data.json`, out of
+/// the way of `list_keys` and regular lookups.
+pub(crate) const TRASH_KEY: &str = "__trash";
+
+/// Oldest trashed entries are dropped once the trash grows past this size.
+const TRASH_CAPACITY: usize = 100;
+
 #[derive(Debug, Error)]
 pub enum YankError {
     #[error("No key provided")]
@@ -23,24 +33,143 @@ pub enum YankError {
     #[error("Failed to parse data file: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("Failed to parse config file: {0}")]
+    Config(#[from] toml::de::Error),
+
     #[error("Clipboard error: {0}")]
-    Clipboard(#[from] arboard::Error),
+    Clipboard(String),
 
     #[error("No value found for '{0}'")]
     KeyNotFound(String),
+
+    #[error("'{0}' already holds a value; cannot use it as a namespace")]
+    NotANamespace(String),
+
+    #[error("Editor '{0}' exited with an error")]
+    EditorFailed(String),
+
+    #[error("Store must be a JSON object")]
+    NotAStore,
+
+    #[error("'{0}' is reserved and cannot be used as a key")]
+    ReservedKey(String),
 }
 
 pub type Result<T> = std::result::Result<T, YankError>;
 
+/// The clipboard backend yank will use to read and write the system clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardProvider {
+    Wayland,
+    X11Xclip,
+    X11Xsel,
+    MacOs,
+    None,
+}
+
+impl ClipboardProvider {
+    /// Resolve the provider to use, honoring a config-pinned backend and
+    /// otherwise probing the environment the same way `copy_to_clipboard`
+    /// does, so `yank provider` reports exactly what yanking would use.
+    pub fn detect(forced: ProviderOverride) -> Self {
+        match forced {
+            ProviderOverride::Auto => Self::probe(),
+            ProviderOverride::WlCopy => ClipboardProvider::Wayland,
+            ProviderOverride::Xclip => ClipboardProvider::X11Xclip,
+            ProviderOverride::Xsel => ClipboardProvider::X11Xsel,
+            ProviderOverride::Pbcopy => ClipboardProvider::MacOs,
+        }
+    }
+
+    fn probe() -> Self {
+        if std::env::var("WAYLAND_DISPLAY").is_ok() && binary_exists("wl-copy") {
+            return ClipboardProvider::Wayland;
+        }
+
+        if std::env::var("DISPLAY").is_ok() {
+            if binary_exists("xclip") {
+                return ClipboardProvider::X11Xclip;
+            }
+            if binary_exists("xsel") {
+                return ClipboardProvider::X11Xsel;
+            }
+        }
+
+        if cfg!(target_os = "macos") && binary_exists("pbcopy") {
+            return ClipboardProvider::MacOs;
+        }
+
+        ClipboardProvider::None
+    }
+}
+
+impl fmt::Display for ClipboardProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipboardProvider::Wayland => write!(f, "wayland (wl-copy)"),
+            ClipboardProvider::X11Xclip => write!(f, "x11 (xclip)"),
+            ClipboardProvider::X11Xsel => write!(f, "x11 (xsel)"),
+            ClipboardProvider::MacOs => write!(f, "macos (pbcopy)"),
+            ClipboardProvider::None => write!(f, "none"),
+        }
+    }
+}
+
+/// The selection a value is copied into.
+///
+/// X11/Wayland distinguish the regular CLIPBOARD selection from the
+/// middle-click PRIMARY selection; macOS's pbcopy has no equivalent to the
+/// latter, so `Primary` falls back to `Clipboard` there with a warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClipboardTarget {
+    #[default]
+    Clipboard,
+    Primary,
+}
+
+/// Recursively collect the full dot-paths of every leaf (non-object) value
+/// under `value`, so e.g. `{"aws": {"prod": {"token": "x"}}}` yields
+/// `aws.prod.token`. Empty namespace objects (left behind once their last
+/// child is deleted) are skipped rather than reported as keys.
+pub(crate) fn collect_leaf_paths(value: &Value, path: String, out: &mut Vec<String>) {
+    match value.as_object() {
+        Some(map) if map.is_empty() => {}
+        Some(map) => {
+            for (key, child) in map {
+                collect_leaf_paths(child, format!("{path}.{key}"), out);
+            }
+        }
+        None => out.push(path),
+    }
+}
+
+fn binary_exists(name: &str) -> bool {
+    Command::new("which")
+        .arg(name)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
 pub struct Handler {
     file_path: PathBuf,
     data: Value,
+    config: Config,
 }
 
 impl Handler {
     pub fn new() -> Result<Self> {
-        let home = dirs::home_dir().ok_or(YankError::HomeDirNotFound)?;
-        let file_path = home.join(".yank/data.json");
+        let config = Config::load()?;
+
+        let file_path = match &config.data_path {
+            Some(path) => path.clone(),
+            None => {
+                let home = dirs::home_dir().ok_or(YankError::HomeDirNotFound)?;
+                home.join(".yank/data.json")
+            }
+        };
 
         if let Some(dir) = file_path.parent() {
             fs::create_dir_all(dir)?;
@@ -49,6 +178,7 @@ impl Handler {
         Ok(Self {
             file_path,
             data: Value::default(),
+            config,
         })
     }
 
@@ -66,36 +196,113 @@ impl Handler {
 
         Ok(())
     }
+
     pub fn save_data(&self) -> Result<()> {
         let content = serde_json::to_string_pretty(&self.data)?;
         fs::write(&self.file_path, content)?;
         Ok(())
     }
 
-    pub fn list_keys(&self) -> Result<()> {
-        let map = match self.data.as_object() {
-            Some(map) if !map.is_empty() => map,
-            _ => {
+    /// List stored keys as their full dot-paths (e.g. `aws.prod.token`), or
+    /// as a nested tree when `tree` is set. `prefix` restricts either mode
+    /// to a subtree, e.g. `aws.` or `aws`.
+    pub fn list_keys(&self, tree: bool, prefix: Option<&str>) -> Result<()> {
+        if tree {
+            let scoped = match prefix.map(|p| p.trim_end_matches('.')).filter(|p| !p.is_empty()) {
+                Some(path) if Self::is_reserved(path) => {
+                    return Err(YankError::ReservedKey(path.to_string()));
+                }
+                Some(path) => Self::navigate(&self.data, path),
+                None => Some(&self.data),
+            };
+
+            let mut printed = false;
+            if let Some(scoped) = scoped {
+                Self::print_tree(scoped, 0, &mut printed);
+            }
+            if !printed {
                 println!("No keys stored.");
-                return Ok(());
             }
+            return Ok(());
+        }
+
+        let mut paths = Vec::new();
+        if let Some(map) = self.data.as_object() {
+            for (key, value) in map {
+                if key == TRASH_KEY {
+                    continue;
+                }
+                collect_leaf_paths(value, key.clone(), &mut paths);
+            }
+        }
+
+        if let Some(prefix) = prefix {
+            let prefix = prefix.trim_end_matches('.');
+            let nested_prefix = format!("{prefix}.");
+            paths.retain(|path| path == prefix || path.starts_with(&nested_prefix));
+        }
+
+        if paths.is_empty() {
+            println!("No keys stored.");
+            return Ok(());
+        }
+
+        paths.sort();
+        for path in paths {
+            println!("{path}");
+        }
+
+        Ok(())
+    }
+
+    fn print_tree(value: &Value, depth: usize, printed: &mut bool) {
+        let Some(map) = value.as_object() else {
+            return;
         };
 
-        let mut keys: Vec<&String> = map.keys().collect();
+        let mut keys: Vec<&String> = map.keys().filter(|key| depth > 0 || *key != TRASH_KEY).collect();
         keys.sort();
 
         for key in keys {
-            println!("{key}");
+            *printed = true;
+            println!("{}{key}", "  ".repeat(depth));
+            Self::print_tree(&map[key], depth + 1, printed);
         }
+    }
 
-        Ok(())
+    /// Whether `key` (or its top-level namespace) collides with the
+    /// reserved `__trash` entry, which must never be read/written/traversed
+    /// through the normal key operations.
+    fn is_reserved(key: &str) -> bool {
+        key.split('.').next() == Some(TRASH_KEY)
+    }
+
+    /// Walk a dot-delimited path (e.g. `aws.prod.token`) down from `data`.
+    fn navigate<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+        let mut current = data;
+        for part in path.split('.') {
+            current = current.as_object()?.get(part)?;
+        }
+        Some(current)
+    }
+
+    /// Mutable counterpart of [`Self::navigate`] used to reach the parent
+    /// object of a leaf before removing it.
+    fn navigate_mut<'a>(data: &'a mut Value, parts: &[&str]) -> Option<&'a mut Value> {
+        let mut current = data;
+        for part in parts {
+            current = current.as_object_mut()?.get_mut(*part)?;
+        }
+        Some(current)
     }
 
     pub fn get_value(&self, key: &str) -> Result<String> {
-        let value = self
-            .data
-            .get(key)
-            .ok_or_else(|| YankError::KeyNotFound(key.to_string()))?;
+        if Self::is_reserved(key) {
+            return Err(YankError::ReservedKey(key.to_string()));
+        }
+
+        let value =
+            Self::navigate(&self.data, key).ok_or_else(|| YankError::KeyNotFound(key.to_string()))?;
 
         Ok(match value {
             Value::String(s) => s.clone(),
@@ -103,40 +310,369 @@ impl Handler {
         })
     }
 
+    /// Walk `namespace` down from `data`, creating any intermediate objects a
+    /// dot-path (e.g. the `aws`, `prod` in `aws.prod.token`) requires.
+    fn ensure_namespace<'a>(data: &'a mut Value, namespace: &[&str]) -> Result<&'a mut Value> {
+        if !matches!(data, Value::Object(_)) {
+            *data = Value::Object(serde_json::Map::new());
+        }
+
+        let mut current = data;
+        for part in namespace {
+            let Value::Object(map) = current else {
+                unreachable!("current is always normalized to an object above");
+            };
+            let entry = map
+                .entry((*part).to_string())
+                .or_insert_with(|| Value::Object(serde_json::Map::new()));
+            if !entry.is_object() {
+                return Err(YankError::NotANamespace((*part).to_string()));
+            }
+            current = entry;
+        }
+
+        Ok(current)
+    }
+
+    /// Store `value` under `key`, creating any intermediate namespace
+    /// objects a dot-path (e.g. `aws.prod.token`) requires.
     pub fn set_value(&mut self, key: &str, value: String) -> Result<()> {
-        if let Value::Object(map) = &mut self.data {
-            map.insert(key.to_string(), Value::String(value));
-        } else {
-            let mut new_map = serde_json::Map::new();
-            new_map.insert(key.to_string(), Value::String(value));
-            self.data = Value::Object(new_map);
+        if Self::is_reserved(key) {
+            return Err(YankError::ReservedKey(key.to_string()));
         }
-        println!("Value set successfully!");
+
+        let parts: Vec<&str> = key.split('.').collect();
+        let (leaf, namespace) = parts
+            .split_last()
+            .expect("str::split always yields at least one part");
+
+        let current = Self::ensure_namespace(&mut self.data, namespace)?;
+        let Value::Object(map) = current else {
+            unreachable!("current is always normalized to an object above");
+        };
+        map.insert((*leaf).to_string(), Value::String(value));
+
         self.save_data()?;
+        println!("Value set successfully!");
         Ok(())
     }
 
-    pub fn delete_value(&mut self, key: &str) -> Result<()> {
-        if let Value::Object(map) = &mut self.data {
-            map.remove(key);
-            println!("Value deleted successfully!");
+    pub fn delete_value(&mut self, key: &str, purge: bool) -> Result<()> {
+        if Self::is_reserved(key) {
+            return Err(YankError::ReservedKey(key.to_string()));
+        }
+
+        let parts: Vec<&str> = key.split('.').collect();
+        let (leaf, namespace) = parts
+            .split_last()
+            .expect("str::split always yields at least one part");
+
+        let parent = Self::navigate_mut(&mut self.data, namespace);
+        let Some(Value::Object(map)) = parent else {
+            println!("Key '{key}' not found");
+            return Ok(());
+        };
+
+        let Some(value) = map.remove(*leaf) else {
+            println!("Key '{key}' not found");
+            return Ok(());
+        };
+
+        if purge {
+            self.save_data()?;
+            println!("Value permanently deleted!");
+            return Ok(());
         }
+
+        let deleted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let trash = self.trash_map_mut();
+        trash.insert(
+            key.to_string(),
+            serde_json::json!({ "value": value, "deleted_at": deleted_at }),
+        );
+        Self::enforce_trash_capacity(trash);
+
         self.save_data()?;
+        println!("Value deleted successfully! (use `yank restore {key}` to undo)");
         Ok(())
     }
 
-    pub fn yank_value(&self, key: &str) -> Result<()> {
+    /// Move a trashed entry back into the live store, recreating any
+    /// namespace (e.g. `aws.prod.` in `aws.prod.token`) the key needs, the
+    /// same way `set_value` does.
+    pub fn restore_value(&mut self, key: &str) -> Result<()> {
+        let Some(entry) = self.trash_map_mut().remove(key) else {
+            println!("Key '{key}' not found in trash");
+            return Ok(());
+        };
+
+        let value = entry.get("value").cloned().unwrap_or(Value::Null);
+
+        let parts: Vec<&str> = key.split('.').collect();
+        let (leaf, namespace) = parts
+            .split_last()
+            .expect("str::split always yields at least one part");
+
+        let current = Self::ensure_namespace(&mut self.data, namespace)?;
+        let Value::Object(map) = current else {
+            unreachable!("current is always normalized to an object above");
+        };
+        map.insert((*leaf).to_string(), value);
+
+        self.save_data()?;
+        println!("Restored '{key}'");
+        Ok(())
+    }
+
+    /// List the keys currently sitting in the trash, most recently deleted first.
+    pub fn list_trash(&self) -> Result<()> {
+        let Some(trash) = self.data.get(TRASH_KEY).and_then(Value::as_object) else {
+            println!("Trash is empty.");
+            return Ok(());
+        };
+
+        if trash.is_empty() {
+            println!("Trash is empty.");
+            return Ok(());
+        }
+
+        let mut entries: Vec<(&String, u64)> = trash
+            .iter()
+            .map(|(key, entry)| {
+                let deleted_at = entry.get("deleted_at").and_then(Value::as_u64).unwrap_or(0);
+                (key, deleted_at)
+            })
+            .collect();
+        entries.sort_by_key(|(_, deleted_at)| std::cmp::Reverse(*deleted_at));
+
+        for (key, deleted_at) in entries {
+            println!("{key}\t(deleted_at: {deleted_at})");
+        }
+
+        Ok(())
+    }
+
+    fn trash_map_mut(&mut self) -> &mut serde_json::Map<String, Value> {
+        if !matches!(self.data, Value::Object(_)) {
+            self.data = Value::Object(serde_json::Map::new());
+        }
+
+        let Value::Object(map) = &mut self.data else {
+            unreachable!("just ensured data is an object");
+        };
+
+        if !matches!(map.get(TRASH_KEY), Some(Value::Object(_))) {
+            map.insert(TRASH_KEY.to_string(), Value::Object(serde_json::Map::new()));
+        }
+
+        map.get_mut(TRASH_KEY)
+            .and_then(|v| v.as_object_mut())
+            .expect("trash entry was just inserted as an object")
+    }
+
+    fn enforce_trash_capacity(trash: &mut serde_json::Map<String, Value>) {
+        while trash.len() > TRASH_CAPACITY {
+            let oldest_key = trash
+                .iter()
+                .min_by_key(|(_, entry)| entry.get("deleted_at").and_then(Value::as_u64).unwrap_or(0))
+                .map(|(key, _)| key.clone());
+
+            match oldest_key {
+                Some(key) => {
+                    trash.remove(&key);
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Read the current system clipboard and store it under `key`.
+    pub fn grab_value(&mut self, key: &str) -> Result<()> {
+        let value = self.read_from_clipboard()?;
+        self.set_value(key, value)?;
+        println!("Grabbed clipboard into '{key}'");
+        Ok(())
+    }
+
+    pub fn yank_value(&self, key: &str, target: ClipboardTarget) -> Result<()> {
         let value = self.get_value(key)?;
+
+        self.copy_to_clipboard(&value, target)?;
+
         println!("{value}");
+        println!("Copied to clipboard!");
+        Ok(())
+    }
+
+    /// Print the clipboard provider that `yank` would use on this system.
+    pub fn show_provider(&self) -> Result<()> {
+        println!("{}", ClipboardProvider::detect(self.config.provider));
+        Ok(())
+    }
 
-        let mut clipboard = Clipboard::new()?;
+    fn copy_to_clipboard(&self, text: &str, target: ClipboardTarget) -> Result<()> {
+        if target == ClipboardTarget::Primary && ClipboardProvider::detect(self.config.provider) == ClipboardProvider::MacOs {
+            eprintln!("Warning: macOS has no PRIMARY selection; falling back to CLIPBOARD");
+        }
 
-        clipboard
-            .set()
-            .wait_until(Instant::now() + Duration::from_millis(100))
-            .text(value)?;
+        match ClipboardProvider::detect(self.config.provider) {
+            ClipboardProvider::Wayland => {
+                let mut cmd = Command::new("wl-copy");
+                if target == ClipboardTarget::Primary {
+                    cmd.arg("--primary");
+                }
+                if cmd.arg(text).status().map(|s| s.success()).unwrap_or(false) {
+                    return Ok(());
+                }
+            }
+            ClipboardProvider::X11Xclip => {
+                let selection = match target {
+                    ClipboardTarget::Clipboard => "clipboard",
+                    ClipboardTarget::Primary => "primary",
+                };
+                if Command::new("xclip")
+                    .args(["-selection", selection])
+                    .stdin(std::process::Stdio::piped())
+                    .spawn()
+                    .and_then(|mut child| {
+                        use std::io::Write;
+                        if let Some(stdin) = child.stdin.as_mut() {
+                            stdin.write_all(text.as_bytes())?;
+                        }
+                        child.wait()
+                    })
+                    .map(|s| s.success())
+                    .unwrap_or(false)
+                {
+                    return Ok(());
+                }
+            }
+            ClipboardProvider::X11Xsel => {
+                let mut args = vec!["--input"];
+                match target {
+                    ClipboardTarget::Clipboard => args.push("--clipboard"),
+                    ClipboardTarget::Primary => args.push("--primary"),
+                }
+                if Command::new("xsel")
+                    .args(&args)
+                    .stdin(std::process::Stdio::piped())
+                    .spawn()
+                    .and_then(|mut child| {
+                        use std::io::Write;
+                        if let Some(stdin) = child.stdin.as_mut() {
+                            stdin.write_all(text.as_bytes())?;
+                        }
+                        child.wait()
+                    })
+                    .map(|s| s.success())
+                    .unwrap_or(false)
+                {
+                    return Ok(());
+                }
+            }
+            ClipboardProvider::MacOs => {
+                if Command::new("pbcopy")
+                    .stdin(std::process::Stdio::piped())
+                    .spawn()
+                    .and_then(|mut child| {
+                        use std::io::Write;
+                        if let Some(stdin) = child.stdin.as_mut() {
+                            stdin.write_all(text.as_bytes())?;
+                        }
+                        child.wait()
+                    })
+                    .map(|s| s.success())
+                    .unwrap_or(false)
+                {
+                    return Ok(());
+                }
+            }
+            ClipboardProvider::None => {}
+        }
 
-        println!("Copied to clipboard!");
+        Err(YankError::Clipboard(
+        "No clipboard utility found. Please install wl-copy (Wayland), xclip/xsel (X11), or pbcopy (macOS)".to_string()))
+    }
+
+    /// Open `data.json` in `$EDITOR` for bulk editing, validating the result
+    /// before it replaces the real store.
+    ///
+    /// The editor is pointed at a temp copy rather than the real file, so a
+    /// crashed editor or invalid JSON never corrupts `data.json`; the temp
+    /// file only replaces it once it parses successfully.
+    pub fn edit_data(&mut self) -> Result<()> {
+        let editor = self.editor_command();
+        let mut parts = editor.split_whitespace();
+        let program = parts.next().unwrap_or("vi");
+        let args: Vec<&str> = parts.collect();
+
+        let temp_path = self.file_path.with_extension("json.tmp");
+        match fs::read_to_string(&self.file_path) {
+            Ok(content) => fs::write(&temp_path, content)?,
+            Err(e) if e.kind() == ErrorKind::NotFound => fs::write(&temp_path, "{}\n")?,
+            Err(e) => return Err(YankError::Io(e)),
+        }
+
+        let status = Command::new(program).args(&args).arg(&temp_path).status()?;
+        if !status.success() {
+            let _ = fs::remove_file(&temp_path);
+            return Err(YankError::EditorFailed(editor));
+        }
+
+        let content = fs::read_to_string(&temp_path)?;
+        let parsed: Value = match serde_json::from_str::<Value>(&content) {
+            Ok(value) if value.is_object() => value,
+            Ok(_) => {
+                let _ = fs::remove_file(&temp_path);
+                return Err(YankError::NotAStore);
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&temp_path);
+                return Err(YankError::Json(e));
+            }
+        };
+
+        fs::rename(&temp_path, &self.file_path)?;
+        self.data = parsed;
+        println!("Store updated.");
         Ok(())
     }
+
+    fn editor_command(&self) -> String {
+        self.config
+            .editor
+            .clone()
+            .or_else(|| std::env::var("VISUAL").ok())
+            .or_else(|| std::env::var("EDITOR").ok())
+            .unwrap_or_else(|| "vi".to_string())
+    }
+
+    fn read_from_clipboard(&self) -> Result<String> {
+        let output = match ClipboardProvider::detect(self.config.provider) {
+            ClipboardProvider::Wayland => Command::new("wl-paste").output().ok(),
+            ClipboardProvider::X11Xclip => Command::new("xclip")
+                .args(["-selection", "clipboard", "-o"])
+                .output()
+                .ok(),
+            ClipboardProvider::X11Xsel => Command::new("xsel")
+                .args(["--clipboard", "--output"])
+                .output()
+                .ok(),
+            ClipboardProvider::MacOs => Command::new("pbpaste").output().ok(),
+            ClipboardProvider::None => None,
+        };
+
+        match output {
+            Some(output) if output.status.success() => {
+                Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+            }
+            _ => Err(YankError::Clipboard(
+                "No clipboard utility found. Please install wl-copy (Wayland), xclip/xsel (X11), or pbcopy (macOS)".to_string(),
+            )),
+        }
+    }
 }
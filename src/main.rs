@@ -1,10 +1,15 @@
-use clap::Parser;
+use std::io;
+
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
 use yank::{
     cli::{Cli, Commands},
-    handler::{Handler, Result},
+    handler::{ClipboardTarget, Handler, Result},
 };
 
 fn main() {
+    CompleteEnv::with_factory(Cli::command).complete();
+
     if let Err(err) = run() {
         eprintln!("{err}");
         std::process::exit(1);
@@ -17,12 +22,29 @@ fn run() -> Result<()> {
     handler.load_data()?;
 
     match cli.command {
-        Some(Commands::Ls) => handler.list_keys()?,
+        Some(Commands::Ls { prefix, tree }) => handler.list_keys(tree, prefix.as_deref())?,
         Some(Commands::Put { key, value }) => handler.set_value(&key, value)?,
-        Some(Commands::Delete { key }) => handler.delete_value(&key)?,
+        Some(Commands::Delete { key, purge }) => handler.delete_value(&key, purge)?,
+        Some(Commands::Restore { key }) => handler.restore_value(&key)?,
+        Some(Commands::Trash) => handler.list_trash()?,
+        Some(Commands::Provider) => handler.show_provider()?,
+        Some(Commands::Grab { key }) => handler.grab_value(&key)?,
+        Some(Commands::Edit) => handler.edit_data()?,
+        Some(Commands::Completions { shell }) => {
+            clap_complete::generate(shell, &mut Cli::command(), "yank", &mut io::stdout());
+        }
+        Some(Commands::Man) => {
+            let man = clap_mangen::Man::new(Cli::command());
+            man.render(&mut io::stdout())?;
+        }
         None => {
             if let Some(key) = cli.key {
-                handler.yank_value(&key)?;
+                let target = if cli.primary {
+                    ClipboardTarget::Primary
+                } else {
+                    ClipboardTarget::Clipboard
+                };
+                handler.yank_value(&key, target)?;
             } else {
                 eprintln!("No key provided");
             }